@@ -1,30 +1,43 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::convert::TryInto;
 use std::fs::File;
 use std::io::prelude::*;
+use std::io::BufWriter;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 ///
-/// Get the sum of digits in a number.
+/// Get the sum of digits in a number, expressed in the given radix.
+///
+/// Extract single digits from a number in base `radix` and returns the sum of its values.
+///
+/// # Panics
+///
+/// Panics if `radix` is less than 2: `radix == 0` would divide by zero, and `radix == 1` would
+/// never reduce `n_rem`, looping forever.
 ///
-/// Extract single digits from a number and returns the sum of its values.
-/// 
 /// ```
 /// use ant25lib::get_digits_sum;
-/// 
-/// assert_eq!(get_digits_sum(0), 0);
-/// assert_eq!(get_digits_sum(123), 6);
-/// if usize::BITS == 32 { assert_eq!(get_digits_sum(999_999_999), 81) };
-/// if usize::BITS == 64 { assert_eq!(get_digits_sum(9_999_999_999_999_999_999), 171) };
+///
+/// assert_eq!(get_digits_sum(0, 10), 0);
+/// assert_eq!(get_digits_sum(123, 10), 6);
+/// assert_eq!(get_digits_sum(0b1011, 2), 3);
+/// if usize::BITS == 32 { assert_eq!(get_digits_sum(999_999_999, 10), 81) };
+/// if usize::BITS == 64 { assert_eq!(get_digits_sum(9_999_999_999_999_999_999, 10), 171) };
 /// ```
-/// 
-pub fn get_digits_sum(n: usize) -> u16 {
+///
+pub fn get_digits_sum(n: usize, radix: u32) -> u16 {
+    assert!(radix >= 2, "get_digits_sum: radix must be at least 2, got {}", radix);
+
+    let radix = radix as usize;
     let mut n_rem = n;
     let mut acc = 0;
 
     while n_rem > 0 {
-        acc += n_rem % 10;
-        n_rem = n_rem / 10;
+        acc += n_rem % radix;
+        n_rem = n_rem / radix;
     }
 
     return acc.try_into().unwrap();
@@ -40,6 +53,38 @@ pub enum FieldCellState {
 
 pub type FieldSparseMatrix = HashMap<usize, HashMap<usize, FieldCellState>>;
 
+///
+/// A pluggable rule for classifying a field cell that has no explicit state set yet.
+///
+/// Implement this to define arbitrary obstacle fields (different thresholds, numeral bases, or
+/// entirely custom predicates) without touching the engine internals. Rules are shared across the
+/// parallel engine, so implementations must be `Send + Sync`.
+///
+pub trait CellRule: Send + Sync {
+    fn classify(&self, x: usize, y: usize) -> FieldCellState;
+}
+
+///
+/// The crate's original obstacle rule: a cell is an `Obstacle` when the sum of `x`'s and `y`'s
+/// digits, expressed in `radix`, exceeds `threshold`; otherwise it's `Clear`.
+///
+/// `radix` must be at least 2 (see `get_digits_sum`); `classify` panics otherwise.
+///
+pub struct DigitSumRule {
+    pub radix: u32,
+    pub threshold: u16,
+}
+
+impl CellRule for DigitSumRule {
+    fn classify(&self, x: usize, y: usize) -> FieldCellState {
+        if get_digits_sum(x, self.radix) + get_digits_sum(y, self.radix) > self.threshold {
+            FieldCellState::Obstacle
+        } else {
+            FieldCellState::Clear
+        }
+    }
+}
+
 ///
 /// Set the state of the specified cell in a field matrix.
 /// 
@@ -74,55 +119,142 @@ pub fn set_cell_state(field: &mut FieldSparseMatrix, x: usize, y: usize, v: Fiel
 /// ```
 /// use ant25lib::*;
 /// use std::collections::HashMap;
-/// 
+///
 /// let mut field = FieldSparseMatrix::new();
-/// assert_eq!(get_cell_state(&mut field, 1000, 1000), FieldCellState::Clear);
-/// assert_eq!(get_cell_state(&mut field, 999, 999),   FieldCellState::Obstacle);
+/// let rule = DigitSumRule { radix: 10, threshold: 25 };
+/// assert_eq!(get_cell_state(&mut field, 1000, 1000, &rule), FieldCellState::Clear);
+/// assert_eq!(get_cell_state(&mut field, 999, 999,   &rule), FieldCellState::Obstacle);
 /// ```
 ///
-pub fn get_cell_state(field: &FieldSparseMatrix, x: usize, y: usize) -> FieldCellState {
+pub fn get_cell_state(field: &FieldSparseMatrix, x: usize, y: usize, rule: &dyn CellRule) -> FieldCellState {
     match field.get(&y) {
         Some(row) =>
             match row.get(&x) {
                 Some(&cell_state) => cell_state,
-                None => calc_field_cell_state(x, y),
+                None => rule.classify(x, y),
             },
-        None => calc_field_cell_state(x, y),
+        None => rule.classify(x, y),
     }
 }
 
 ///
 /// Mark cells in a field sparse matrix with Avail state when ants can to pass from the starting point.
-/// 
-/// Marks cells in the `field` sparse matrix with Avail state, when ants can to pass straight from the 
+///
+/// Marks cells in the `field` sparse matrix with Avail state, when ants can to pass straight from the
 /// specified starting point in `x` and `y` into that cells.
-/// 
-/// WARNING: May require extended stack capacity for process/thread to process wide field areas 
-/// since it's recursive by nature.
-
+///
+/// Implemented as an iterative worklist rather than recursion, so the reachable region can grow
+/// arbitrarily large without growing the call stack. Neighbor coordinates are computed with
+/// checked arithmetic, so flood filling never panics near the edges of the coordinate space.
+///
 /// ```
 /// use ant25lib::*;
 /// use std::collections::HashMap;
-/// 
+///
 /// let mut field = FieldSparseMatrix::new();
-/// mark_cells_avail_for_ant(&mut field, 742, 703);
+/// let rule = DigitSumRule { radix: 10, threshold: 25 };
+/// mark_cells_avail_for_ant(&mut field, 742, 703, &rule);
 /// ```
-/// 
-pub fn mark_cells_avail_for_ant(field: &mut FieldSparseMatrix, x: usize, y: usize) {
-    if get_cell_state(field, x, y) != FieldCellState::Clear { return; }
+///
+pub fn mark_cells_avail_for_ant(field: &mut FieldSparseMatrix, x: usize, y: usize, rule: &dyn CellRule) {
+    let mut worklist: Vec<(usize, usize)> = vec![(x, y)];
 
-    set_cell_state(field, x, y, FieldCellState::Avail);
+    while let Some((cur_x, cur_y)) = worklist.pop() {
+        if get_cell_state(field, cur_x, cur_y, rule) != FieldCellState::Clear { continue; }
 
-    if y <= usize::MAX { mark_cells_avail_for_ant(field, x, y + 1) };
-    if y >  usize::MIN { mark_cells_avail_for_ant(field, x, y - 1) };
-    if x >  usize::MIN { mark_cells_avail_for_ant(field, x - 1, y) };
-    if x <= usize::MAX { mark_cells_avail_for_ant(field, x + 1, y) };
+        set_cell_state(field, cur_x, cur_y, FieldCellState::Avail);
+
+        if let Some(y_plus)  = cur_y.checked_add(1) { worklist.push((cur_x, y_plus)) };
+        if let Some(y_minus) = cur_y.checked_sub(1) { worklist.push((cur_x, y_minus)) };
+        if let Some(x_minus) = cur_x.checked_sub(1) { worklist.push((x_minus, cur_y)) };
+        if let Some(x_plus)  = cur_x.checked_add(1) { worklist.push((x_plus, cur_y)) };
+    }
+}
+
+///
+/// Mark cells in a field sparse matrix with Avail state using several worker threads.
+///
+/// Behaves exactly like `mark_cells_avail_for_ant`, but spreads the work across `num_threads`
+/// threads sharing the `field` behind a `Mutex`. A work queue shared behind its own `Mutex` holds
+/// the frontier of cells still to visit, and an atomic in-flight counter lets workers detect when
+/// the queue is both empty and has no outstanding work, at which point they stop.
+///
+/// # Panics
+///
+/// Panics if `num_threads` is 0: with no workers spawned, the seeded queue entry would never be
+/// consumed and `field` would silently come back unchanged.
+///
+/// ```
+/// use ant25lib::*;
+/// use std::sync::Arc;
+///
+/// let mut field = FieldSparseMatrix::new();
+/// let rule: Arc<dyn CellRule> = Arc::new(DigitSumRule { radix: 10, threshold: 25 });
+/// mark_cells_avail_for_ant_parallel(&mut field, 742, 703, 4, rule);
+/// ```
+///
+pub fn mark_cells_avail_for_ant_parallel(field: &mut FieldSparseMatrix, x: usize, y: usize, num_threads: usize, rule: Arc<dyn CellRule>) {
+    assert!(num_threads > 0, "mark_cells_avail_for_ant_parallel: num_threads must be at least 1");
+
+    let shared_field = Arc::new(Mutex::new(std::mem::take(field)));
+    let queue = Arc::new(Mutex::new(VecDeque::from([(x, y)])));
+    let in_flight = Arc::new(AtomicUsize::new(1));
+
+    let workers: Vec<_> = (0..num_threads).map(|_| {
+        let shared_field = Arc::clone(&shared_field);
+        let queue = Arc::clone(&queue);
+        let in_flight = Arc::clone(&in_flight);
+        let rule = Arc::clone(&rule);
+
+        thread::spawn(move || loop {
+            let cell = queue.lock().unwrap().pop_front();
+
+            let (cur_x, cur_y) = match cell {
+                Some(cell) => cell,
+                None => {
+                    if in_flight.load(Ordering::SeqCst) == 0 { break; }
+                    thread::yield_now();
+                    continue;
+                }
+            };
+
+            let became_avail = {
+                let mut field = shared_field.lock().unwrap();
+                if get_cell_state(&field, cur_x, cur_y, rule.as_ref()) == FieldCellState::Clear {
+                    set_cell_state(&mut field, cur_x, cur_y, FieldCellState::Avail);
+                    true
+                } else {
+                    false
+                }
+            };
+
+            if became_avail {
+                let mut neighbors = Vec::with_capacity(4);
+                if let Some(y_plus)  = cur_y.checked_add(1) { neighbors.push((cur_x, y_plus)) };
+                if let Some(y_minus) = cur_y.checked_sub(1) { neighbors.push((cur_x, y_minus)) };
+                if let Some(x_minus) = cur_x.checked_sub(1) { neighbors.push((x_minus, cur_y)) };
+                if let Some(x_plus)  = cur_x.checked_add(1) { neighbors.push((x_plus, cur_y)) };
+
+                in_flight.fetch_add(neighbors.len(), Ordering::SeqCst);
+                queue.lock().unwrap().extend(neighbors);
+            }
+
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+        })
+    }).collect();
+
+    for worker in workers { worker.join().unwrap(); }
+
+    *field = Arc::try_unwrap(shared_field).unwrap().into_inner().unwrap();
 }
 
 //
 // Calculate available cells count to pass an ant in field sparse matrix.
 //
-pub fn count_cells_avail_for_ant(field: &FieldSparseMatrix) -> usize {
+// Takes a `CellRule` for signature parity with the rest of the engine, even though counting
+// already-set `Avail` cells never needs to classify an unset one.
+//
+pub fn count_cells_avail_for_ant(field: &FieldSparseMatrix, _rule: &dyn CellRule) -> usize {
     field.iter().map(|(_, row)| 
         row.iter().map(|(_,&cell)| if cell == FieldCellState::Avail { 1 } else { 0 })
         .reduce(|acc, x| acc + x).unwrap_or_default()
@@ -132,7 +264,7 @@ pub fn count_cells_avail_for_ant(field: &FieldSparseMatrix) -> usize {
 //
 // Write an image in PPM format to file from a field sparse matrix.
 //
-pub fn write_ppm(field: &FieldSparseMatrix, filename: &str, x_start: usize, y_start: usize, x_end: usize, y_end:usize) {
+pub fn write_ppm(field: &FieldSparseMatrix, filename: &str, x_start: usize, y_start: usize, x_end: usize, y_end: usize, rule: &dyn CellRule) {
     let path = Path::new(&filename);
 
     // Open a file in write-only mode, returns `io::Result<File>`
@@ -148,22 +280,131 @@ pub fn write_ppm(field: &FieldSparseMatrix, filename: &str, x_start: usize, y_st
 
     for y in y_start..y_end+1 {
         for x in x_start..x_end+1 {
-            file.write(&map_state_to_color(get_cell_state(&field, x, y))).unwrap(); 
+            file.write(&map_state_to_color(get_cell_state(&field, x, y, rule))).unwrap();
         }
     };
 }
 
 ///
-/// Calculate predicate: is it the cell clear to pass for an ant or is it depricated for pass? 
-/// 
-fn calc_field_cell_state(x: usize, y: usize) -> FieldCellState {
-    if get_digits_sum(x) + get_digits_sum(y) > 25  { 
-        FieldCellState::Obstacle 
-    } else { 
-        FieldCellState::Clear 
+/// A rectangular window into a field, given as inclusive `x`/`y` bounds.
+///
+pub struct Window {
+    pub x_start: usize,
+    pub y_start: usize,
+    pub x_end: usize,
+    pub y_end: usize,
+}
+
+//
+// Write an image in PPM format to file from a field sparse matrix, streaming it out in
+// horizontal bands of `tile_height` rows through a buffered writer instead of holding the whole
+// window in memory at once. Rows are drained from `field` as soon as their band has been
+// flushed, so the sparse matrix shrinks as the export proceeds. Rows of the window not yet
+// reached stay in `field` until their band is written, and rows outside the window are not
+// removed.
+//
+pub fn write_ppm_tiled(field: &mut FieldSparseMatrix, filename: &str, window: Window, tile_height: usize, rule: &dyn CellRule) {
+    assert!(tile_height > 0, "write_ppm_tiled: tile_height must be at least 1");
+
+    let Window { x_start, y_start, x_end, y_end } = window;
+    let path = Path::new(&filename);
+
+    let file = match File::create(&path) {
+        Err(why) => panic!("couldn't create {}: {}", path.display(), why),
+        Ok(file) => file,
+    };
+    let mut writer = BufWriter::new(file);
+
+    writer.write_all("P6\n".as_bytes()).unwrap();
+    writer.write_all(format!("{} {}\n", x_end - x_start + 1, y_end - y_start + 1).as_bytes()).unwrap();
+    writer.write_all("255\n".as_bytes()).unwrap();
+
+    let mut band_start = y_start;
+
+    loop {
+        let band_end = band_start.saturating_add(tile_height - 1).min(y_end);
+
+        for y in band_start..=band_end {
+            for x in x_start..=x_end {
+                writer.write_all(&map_state_to_color(get_cell_state(&field, x, y, rule))).unwrap();
+            }
+        }
+
+        writer.flush().unwrap();
+
+        for y in band_start..=band_end {
+            field.remove(&y);
+        }
+
+        if band_end == y_end { break; }
+        band_start = band_end + 1;
     }
 }
 
+//
+// Read a field sparse matrix back from a PPM image written by `write_ppm`.
+//
+// Parses the P6 header to recover the image dimensions, then reads the pixel body and maps
+// each RGB triple back through the inverse of `map_state_to_color`. Returns the reconstructed
+// field along with the `x` and `y` dimensions read from the header.
+//
+pub fn read_ppm(filename: &str) -> (FieldSparseMatrix, usize, usize) {
+    let path = Path::new(&filename);
+
+    let mut file = match File::open(&path) {
+        Err(why) => panic!("couldn't open {}: {}", path.display(), why),
+        Ok(file) => file,
+    };
+
+    let mut magic = [0u8; 2];
+    file.read_exact(&mut magic).unwrap();
+    assert_eq!(&magic, b"P6", "{} is not a P6 PPM image", path.display());
+
+    let x_dims = read_ppm_uint(&mut file);
+    let y_dims = read_ppm_uint(&mut file);
+    let _maxval = read_ppm_uint(&mut file);
+
+    let mut field = FieldSparseMatrix::new();
+    let mut pixel = [0u8; 3];
+
+    for y in 0..y_dims {
+        for x in 0..x_dims {
+            file.read_exact(&mut pixel).unwrap();
+            set_cell_state(&mut field, x, y, map_color_to_state(pixel));
+        }
+    }
+
+    (field, x_dims, y_dims)
+}
+
+//
+// Decode the next whitespace-delimited decimal field from a PPM header byte stream, skipping
+// any leading whitespace (and `#` comment lines, per the PPM spec) before accumulating digits
+// most-significant-first.
+//
+fn read_ppm_uint(file: &mut File) -> usize {
+    let mut byte = [0u8; 1];
+
+    loop {
+        file.read_exact(&mut byte).unwrap();
+        if byte[0] == b'#' {
+            while byte[0] != b'\n' { file.read_exact(&mut byte).unwrap(); }
+            continue;
+        }
+        if !(byte[0] as char).is_whitespace() { break; }
+    }
+
+    let mut acc: usize = (byte[0] - b'0') as usize;
+
+    loop {
+        file.read_exact(&mut byte).unwrap();
+        if !(byte[0] as char).is_ascii_digit() { break; }
+        acc = acc * 10 + (byte[0] - b'0') as usize;
+    }
+
+    acc
+}
+
 //
 //
 //
@@ -179,6 +420,18 @@ fn map_state_to_color(x: FieldCellState) -> [u8; 3] {
     }
 }
 
+//
+//
+//
+fn map_color_to_state(c: [u8; 3]) -> FieldCellState {
+    match c {
+        [0xFF, 0xFF, 0x00] => FieldCellState::Clear,
+        [0x00, 0x00, 0xFF] => FieldCellState::Obstacle,
+        [0x00, 0xFF, 0x00] => FieldCellState::Avail,
+        _ => panic!("unrecognized cell color: {:?}", c),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,23 +439,24 @@ mod tests {
     #[test]
     fn test_field_cell_state_set_and_get_fn() {
         let mut field = FieldSparseMatrix::new();
+        let rule = DigitSumRule { radix: 10, threshold: 25 };
 
-        assert_eq!(get_cell_state(&field, 10,   10  ), FieldCellState::Clear);
-        assert_eq!(get_cell_state(&field, 101,  101 ), FieldCellState::Clear);
-        assert_eq!(get_cell_state(&field, 1234, 4321), FieldCellState::Clear);
-        assert_eq!(get_cell_state(&field, 999,  999 ), FieldCellState::Obstacle);
+        assert_eq!(get_cell_state(&field, 10,   10,   &rule), FieldCellState::Clear);
+        assert_eq!(get_cell_state(&field, 101,  101,  &rule), FieldCellState::Clear);
+        assert_eq!(get_cell_state(&field, 1234, 4321, &rule), FieldCellState::Clear);
+        assert_eq!(get_cell_state(&field, 999,  999,  &rule), FieldCellState::Obstacle);
 
         set_cell_state(&mut field, 10,   10,   FieldCellState::Obstacle);
         set_cell_state(&mut field, 101,  101,  FieldCellState::Clear);
         set_cell_state(&mut field, 1234, 4321, FieldCellState::Avail);
-    
+
         assert_eq!(field[&10][&10],     FieldCellState::Obstacle);
         assert_eq!(field[&101][&101],   FieldCellState::Clear);
         assert_eq!(field[&4321][&1234], FieldCellState::Avail);
 
-        assert_eq!(get_cell_state(&field, 10,   10  ), FieldCellState::Obstacle);
-        assert_eq!(get_cell_state(&field, 101,  101 ), FieldCellState::Clear);
-        assert_eq!(get_cell_state(&field, 1234, 4321), FieldCellState::Avail);
+        assert_eq!(get_cell_state(&field, 10,   10,   &rule), FieldCellState::Obstacle);
+        assert_eq!(get_cell_state(&field, 101,  101,  &rule), FieldCellState::Clear);
+        assert_eq!(get_cell_state(&field, 1234, 4321, &rule), FieldCellState::Avail);
 
         assert_eq!(field.len(), 3);
         for (_y, row) in field {
@@ -213,6 +467,7 @@ mod tests {
     #[test]
     fn test_mark_calc_cells_avail_for_ant() {
         let mut field = FieldSparseMatrix::new();
+        let rule = DigitSumRule { radix: 10, threshold: 25 };
 
         let cage: [&str; 5] = [
             "XXXX**XXXX",
@@ -221,16 +476,16 @@ mod tests {
             "****X****X",
             "XXXXXXXXX ",
         ];
-    
+
         for y in 0..5 {
             let row: Vec<char> = cage[y].chars().collect();
             for x in 0..10  {
                 set_cell_state(&mut field, x, y, if row[x] == 'X' {FieldCellState::Obstacle} else {FieldCellState::Clear})
             }
         }
-                      
-        mark_cells_avail_for_ant(&mut field, 4, 2);
-    
+
+        mark_cells_avail_for_ant(&mut field, 4, 2, &rule);
+
         let mut avail_cells_count = 0;
 
         for y in 0..5 {
@@ -244,7 +499,129 @@ mod tests {
             }
         }
 
-        assert_eq!(count_cells_avail_for_ant(&field), avail_cells_count);
+        assert_eq!(count_cells_avail_for_ant(&field, &rule), avail_cells_count);
+    }
+
+    #[test]
+    fn test_mark_cells_avail_for_ant_parallel_matches_sequential() {
+        let cage: [&str; 5] = [
+            "XXXX**XXXX",
+            "X********X",
+            "X**X**X**X",
+            "****X****X",
+            "XXXXXXXXX ",
+        ];
+
+        let make_field = || {
+            let mut field = FieldSparseMatrix::new();
+            for y in 0..5 {
+                let row: Vec<char> = cage[y].chars().collect();
+                for x in 0..10 {
+                    set_cell_state(&mut field, x, y, if row[x] == 'X' {FieldCellState::Obstacle} else {FieldCellState::Clear})
+                }
+            }
+            field
+        };
+        let rule = DigitSumRule { radix: 10, threshold: 25 };
+
+        let mut sequential = make_field();
+        mark_cells_avail_for_ant(&mut sequential, 4, 2, &rule);
+
+        let mut parallel = make_field();
+        let rule_arc: Arc<dyn CellRule> = Arc::new(DigitSumRule { radix: 10, threshold: 25 });
+        mark_cells_avail_for_ant_parallel(&mut parallel, 4, 2, 4, rule_arc);
+
+        assert_eq!(count_cells_avail_for_ant(&parallel, &rule), count_cells_avail_for_ant(&sequential, &rule));
+
+        for y in 0..5 {
+            for x in 0..10 {
+                assert_eq!(parallel[&y][&x], sequential[&y][&x]);
+            }
+        }
+    }
+
+    fn make_cage_field() -> FieldSparseMatrix {
+        let cage: [&str; 5] = [
+            "XXXX**XXXX",
+            "X********X",
+            "X**X**X**X",
+            "****X****X",
+            "XXXXXXXXX ",
+        ];
+
+        let mut field = FieldSparseMatrix::new();
+        for y in 0..5 {
+            let row: Vec<char> = cage[y].chars().collect();
+            for x in 0..10 {
+                set_cell_state(&mut field, x, y, if row[x] == 'X' {FieldCellState::Obstacle} else {FieldCellState::Clear})
+            }
+        }
+        field
+    }
+
+    #[test]
+    fn test_write_then_read_ppm_round_trip() {
+        let rule = DigitSumRule { radix: 10, threshold: 25 };
+        let mut field = make_cage_field();
+        mark_cells_avail_for_ant(&mut field, 4, 2, &rule);
+
+        let path = std::env::temp_dir().join("ant25lib_test_round_trip.ppm");
+        let path = path.to_str().unwrap();
+
+        write_ppm(&field, path, 0, 0, 9, 4, &rule);
+        let (field_read_back, x_dims, y_dims) = read_ppm(path);
+
+        assert_eq!(x_dims, 10);
+        assert_eq!(y_dims, 5);
+
+        for y in 0..5 {
+            for x in 0..10 {
+                assert_eq!(field_read_back[&y][&x], field[&y][&x]);
+            }
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_write_ppm_tiled_matches_write_ppm_and_drains_field() {
+        let rule = DigitSumRule { radix: 10, threshold: 25 };
+
+        let mut field_for_whole = make_cage_field();
+        mark_cells_avail_for_ant(&mut field_for_whole, 4, 2, &rule);
+
+        let mut field_for_tiled = make_cage_field();
+        mark_cells_avail_for_ant(&mut field_for_tiled, 4, 2, &rule);
+
+        let whole_path = std::env::temp_dir().join("ant25lib_test_tiled_whole.ppm");
+        let tiled_path = std::env::temp_dir().join("ant25lib_test_tiled_banded.ppm");
+        let whole_path = whole_path.to_str().unwrap();
+        let tiled_path = tiled_path.to_str().unwrap();
+
+        write_ppm(&field_for_whole, whole_path, 0, 0, 9, 4, &rule);
+
+        let window = Window { x_start: 0, y_start: 0, x_end: 9, y_end: 4 };
+        write_ppm_tiled(&mut field_for_tiled, tiled_path, window, 2, &rule);
+
+        assert!(field_for_tiled.is_empty(), "write_ppm_tiled should drain every row once its band is flushed");
+        assert_eq!(std::fs::read(whole_path).unwrap(), std::fs::read(tiled_path).unwrap());
+
+        std::fs::remove_file(whole_path).unwrap();
+        std::fs::remove_file(tiled_path).unwrap();
+    }
+
+    #[test]
+    fn test_write_ppm_tiled_does_not_overflow_at_usize_max() {
+        let rule = DigitSumRule { radix: 10, threshold: 25 };
+        let mut field = FieldSparseMatrix::new();
+
+        let path = std::env::temp_dir().join("ant25lib_test_tiled_usize_max.ppm");
+        let path = path.to_str().unwrap();
+
+        let window = Window { x_start: 0, y_start: usize::MAX, x_end: 0, y_end: usize::MAX };
+        write_ppm_tiled(&mut field, path, window, 5, &rule);
+
+        std::fs::remove_file(path).unwrap();
     }
 
 }
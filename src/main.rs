@@ -5,13 +5,14 @@ fn main() {
     let args: Vec<String> = env::args().collect();
 
     let mut field = FieldSparseMatrix::new();
+    let rule = DigitSumRule { radix: 10, threshold: 25 };
 
     println!("Calculating available cells...");
-    mark_cells_avail_for_ant(&mut field, 1000, 1000);
-    println!("Available cells count: {}.", count_cells_avail_for_ant(&field));
+    mark_cells_avail_for_ant(&mut field, 1000, 1000, &rule);
+    println!("Available cells count: {}.", count_cells_avail_for_ant(&field, &rule));
 
     if args.len() == 2 {
         println!("Writing PPM-image into {}...", &args[1]);
-        write_ppm(&field, &args[1], 500, 500, 2499, 2499);
+        write_ppm(&field, &args[1], 500, 500, 2499, 2499, &rule);
     }
 }